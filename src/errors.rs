@@ -59,4 +59,32 @@ pub enum ContractError {
     /// Daily send limit exceeded for this user.
     /// Cause: User's total transfers in the last 24 hours exceed the configured limit.
     DailySendLimitExceeded = 23,
+
+    /// Settlement hash chain verification failed.
+    /// Cause: Recomputed link does not match the stored settlement hash, indicating
+    /// the settlement record or a prior link in the chain was tampered with.
+    HashChainMismatch = 24,
+
+    /// Persistent entry is missing when a proactive TTL refresh was expected to find one.
+    /// Cause: Calling `touch_remittance`/`bump_user_data` for an entry that was never
+    /// written, or whose TTL ran out and was archived by the ledger. Soroban does not
+    /// let a contract distinguish these two cases once the entry is gone, so both are
+    /// reported the same way.
+    EntryArchived = 26,
+
+    /// Submitted nonce does not match the sender's expected next nonce.
+    /// Cause: Replaying a previously-used settlement authorization, or submitting
+    /// out of order.
+    InvalidNonce = 27,
+
+    /// No migration is currently active.
+    /// Cause: Calling `import_batch()` or `finalize_migration()` before `begin_migration()`,
+    /// or after a migration has already been finalized.
+    MigrationNotActive = 28,
+
+    /// No settlement hash is recorded for this remittance ID.
+    /// Cause: Calling `verify_settlement()` for an ID that has never been settled (or
+    /// carried over from a migration), as opposed to one whose recorded hash fails to
+    /// match the recomputed link.
+    SettlementNotFound = 29,
 }
@@ -5,7 +5,7 @@
 //! Uses both instance storage (contract-level config) and persistent storage
 //! (per-entity data).
 
-use soroban_sdk::{contracttype, Address, Env, String, Vec};
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, String, Vec};
 
 use crate::{ContractError, Remittance, TransferRecord, DailyLimit};
 
@@ -13,7 +13,7 @@ use crate::{ContractError, Remittance, TransferRecord, DailyLimit};
 ///
 /// Storage Layout:
 /// - Instance storage: Contract-level configuration and state (Admin, UsdcToken, PlatformFeeBps,
-///   RemittanceCounter, AccumulatedFees)
+///   RemittanceCounter, per-token AccumulatedFees)
 /// - Persistent storage: Per-entity data that needs long-term retention (Remittance records,
 ///   AgentRegistered status)
 #[contracttype]
@@ -51,17 +51,22 @@ enum DataKey {
 
     // === Fee Tracking ===
     // Keys for managing platform fees
-    /// Total accumulated platform fees awaiting withdrawal
-    AccumulatedFees,
+    /// Accumulated platform fees awaiting withdrawal, indexed by the token they
+    /// were collected in (instance storage)
+    AccumulatedFees(Address),
 
-    /// Contract pause status for emergency halts
+    /// Per-operation pause bitmask for granular emergency halts (instance storage)
     Paused,
 
     // === Settlement Deduplication ===
     // Keys for preventing duplicate settlement execution
-    /// Settlement hash for duplicate detection (persistent storage)
+    /// Settlement hash-chain link for the given remittance ID, used both for
+    /// duplicate detection and tamper-evidence (persistent storage)
     SettlementHash(u64),
-    
+
+    /// Current head of the settlement hash chain (instance storage)
+    HashChainHead,
+
     // === Rate Limiting ===
     // Keys for preventing abuse through rate limiting
     /// Cooldown period in seconds between settlements per sender
@@ -82,6 +87,76 @@ enum DataKey {
     // Keys for managing whitelisted tokens
     /// Token whitelist status indexed by token address (persistent storage)
     TokenWhitelisted(Address),
+
+    // === Migration ===
+    // Keys for batched migration of remittance state to an upgraded contract
+    /// Current migration state, if a migration has ever been started (instance storage)
+    MigrationState,
+
+    // === TTL Management ===
+    // Keys for controlling how long persistent entries are kept alive
+    /// TTL bump/extension configuration applied to persistent writes (instance storage)
+    TtlConfig,
+
+    // === Replay Protection ===
+    // Keys for preventing settlement authorizations from being replayed
+    /// Last consumed nonce for a sender address (persistent storage)
+    SenderNonce(Address),
+}
+
+// === TTL Management ===
+//
+// Soroban does not let a contract tell "never written" and "archived after TTL
+// expiry" apart once an entry is gone: a live entry's remaining TTL can be read
+// before it expires, but an archived entry is indistinguishable from one that
+// never existed without an off-chain index the contract doesn't keep. So reads
+// like `get_remittance`/`get_user_transfers` keep their existing not-found
+// semantics, and only the proactive refresh helpers below (`touch_remittance`,
+// `bump_user_data`) surface `ContractError::EntryArchived` for a missing entry,
+// since by construction they're only ever called for entries that should
+// already exist.
+
+/// Rent-style TTL configuration applied to persistent storage writes.
+///
+/// Modeled on Soroban's state-expiration rent accounting: each time a persistent
+/// entry is written or touched, if its remaining TTL is below `bump_threshold`
+/// ledgers, it is extended back out to `extend_to` ledgers.
+#[contracttype]
+#[derive(Clone)]
+pub struct TtlConfig {
+    pub bump_threshold: u32,
+    pub extend_to: u32,
+}
+
+/// Retrieves the configured TTL bump thresholds, falling back to a
+/// conservative default if none has been set.
+pub fn get_ttl_config(env: &Env) -> TtlConfig {
+    env.storage().instance().get(&DataKey::TtlConfig).unwrap_or(TtlConfig {
+        bump_threshold: 17_280,  // ~1 day at 5s ledgers
+        extend_to: 518_400,      // ~30 days at 5s ledgers
+    })
+}
+
+/// Sets the TTL bump thresholds applied to subsequent persistent writes.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `config` - Bump threshold and extension target, in ledgers
+pub fn set_ttl_config(env: &Env, config: &TtlConfig) {
+    env.storage().instance().set(&DataKey::TtlConfig, config);
+}
+
+/// Extends a persistent entry's TTL using the configured bump thresholds.
+/// Called after each persistent write of remittance, agent, settlement-hash,
+/// rate-limit, daily-limit, transfer-history and nonce entries so long-lived
+/// records do not silently expire and become unreachable. Admin-role and
+/// token-whitelist writes do not extend their TTL.
+fn extend_persistent_ttl(env: &Env, key: &DataKey) {
+    let config = get_ttl_config(env);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, config.bump_threshold, config.extend_to);
 }
 
 /// Checks if the contract has an admin configured.
@@ -218,9 +293,32 @@ pub fn get_remittance_counter(env: &Env) -> Result<u64, ContractError> {
 /// * `id` - Remittance ID
 /// * `remittance` - Remittance record to store
 pub fn set_remittance(env: &Env, id: u64, remittance: &Remittance) {
-    env.storage()
-        .persistent()
-        .set(&DataKey::Remittance(id), remittance);
+    let key = DataKey::Remittance(id);
+    env.storage().persistent().set(&key, remittance);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Proactively refreshes a remittance record's TTL without rewriting it.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `id` - Remittance ID to refresh
+///
+/// # Errors
+///
+/// * `ContractError::EntryArchived` - No remittance record is currently readable for
+///   this ID (see the TTL Management notes above on why this can't be distinguished
+///   from the record never having existed)
+pub fn touch_remittance(env: &Env, id: u64) -> Result<(), ContractError> {
+    let key = DataKey::Remittance(id);
+
+    if !env.storage().persistent().has(&key) {
+        return Err(ContractError::EntryArchived);
+    }
+
+    extend_persistent_ttl(env, &key);
+    Ok(())
 }
 
 /// Retrieves a remittance record by ID.
@@ -249,9 +347,9 @@ pub fn get_remittance(env: &Env, id: u64) -> Result<Remittance, ContractError> {
 /// * `agent` - Agent address
 /// * `registered` - Registration status (true = registered, false = removed)
 pub fn set_agent_registered(env: &Env, agent: &Address, registered: bool) {
-    env.storage()
-        .persistent()
-        .set(&DataKey::AgentRegistered(agent.clone()), &registered);
+    let key = DataKey::AgentRegistered(agent.clone());
+    env.storage().persistent().set(&key, &registered);
+    extend_persistent_ttl(env, &key);
 }
 
 /// Checks if an address is registered as an agent.
@@ -272,33 +370,49 @@ pub fn is_agent_registered(env: &Env, agent: &Address) -> bool {
         .unwrap_or(false)
 }
 
-/// Sets the accumulated platform fees.
+/// Retrieves the accumulated platform fees for a specific whitelisted token.
 ///
 /// # Arguments
 ///
 /// * `env` - The contract execution environment
-/// * `fees` - Total accumulated fees
-pub fn set_accumulated_fees(env: &Env, fees: i128) {
+/// * `token` - Token address to look up
+///
+/// # Returns
+///
+/// Accumulated fees for this token, or `0` if none have ever been recorded.
+pub fn get_accumulated_fees_for(env: &Env, token: &Address) -> i128 {
     env.storage()
         .instance()
-        .set(&DataKey::AccumulatedFees, &fees);
+        .get(&DataKey::AccumulatedFees(token.clone()))
+        .unwrap_or(0)
 }
 
-/// Retrieves the accumulated platform fees.
+/// Adds to the accumulated platform fees for a specific whitelisted token.
 ///
 /// # Arguments
 ///
 /// * `env` - The contract execution environment
+/// * `token` - Token address the fee was collected in
+/// * `amount` - Amount to add to that token's accrued fee pool
+pub fn add_accumulated_fees(env: &Env, token: &Address, amount: i128) {
+    let total = get_accumulated_fees_for(env, token) + amount;
+    env.storage()
+        .instance()
+        .set(&DataKey::AccumulatedFees(token.clone()), &total);
+}
+
+/// Sets the accumulated platform fees for a specific whitelisted token,
+/// e.g. to zero it out after a withdrawal.
 ///
-/// # Returns
+/// # Arguments
 ///
-/// * `Ok(i128)` - Total accumulated fees
-/// * `Err(ContractError::NotInitialized)` - Contract not initialized
-pub fn get_accumulated_fees(env: &Env) -> Result<i128, ContractError> {
+/// * `env` - The contract execution environment
+/// * `token` - Token address to update
+/// * `amount` - New accrued fee balance for this token
+pub fn set_accumulated_fees_for(env: &Env, token: &Address, amount: i128) {
     env.storage()
         .instance()
-        .get(&DataKey::AccumulatedFees)
-        .ok_or(ContractError::NotInitialized)
+        .set(&DataKey::AccumulatedFees(token.clone()), &amount);
 }
 
 /// Checks if a settlement hash exists for duplicate detection.
@@ -318,27 +432,208 @@ pub fn has_settlement_hash(env: &Env, remittance_id: u64) -> bool {
         .has(&DataKey::SettlementHash(remittance_id))
 }
 
-/// Marks a settlement as executed for duplicate prevention.
+/// Retrieves the settlement hash-chain link recorded for a remittance.
 ///
 /// # Arguments
 ///
 /// * `env` - The contract execution environment
-/// * `remittance_id` - Remittance ID to mark as settled
-pub fn set_settlement_hash(env: &Env, remittance_id: u64) {
+/// * `remittance_id` - Remittance ID to look up
+///
+/// # Returns
+///
+/// * `Some(BytesN<32>)` - The hash-chain link stored for this settlement
+/// * `None` - No settlement has been recorded for this ID
+pub fn get_settlement_hash(env: &Env, remittance_id: u64) -> Option<BytesN<32>> {
     env.storage()
         .persistent()
-        .set(&DataKey::SettlementHash(remittance_id), &true);
+        .get(&DataKey::SettlementHash(remittance_id))
 }
 
-pub fn is_paused(env: &Env) -> bool {
+/// Retrieves the current head of the settlement hash chain.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+///
+/// # Returns
+///
+/// The current chain head, or an all-zero hash if no settlement has been recorded yet.
+pub fn get_hash_chain_head(env: &Env) -> BytesN<32> {
     env.storage()
         .instance()
-        .get(&DataKey::Paused)
-        .unwrap_or(false)
+        .get(&DataKey::HashChainHead)
+        .unwrap_or_else(|| BytesN::from_array(env, &[0u8; 32]))
+}
+
+fn set_hash_chain_head(env: &Env, head: &BytesN<32>) {
+    env.storage().instance().set(&DataKey::HashChainHead, head);
+}
+
+/// Computes the next settlement hash-chain link.
+///
+/// `H_n = sha256(H_{n-1} || contract_address || network_id || remittance_id_le
+///   || sender || recipient || amount_le || timestamp_le)`
+///
+/// Binding the contract's own address and the ledger network ID domain-separates
+/// the chain so a settlement authorization cannot be lifted and replayed against
+/// a redeployed or forked contract.
+fn compute_settlement_hash(
+    env: &Env,
+    prev: &BytesN<32>,
+    remittance_id: u64,
+    sender: &Address,
+    recipient: &Address,
+    amount: i128,
+    timestamp: u64,
+) -> BytesN<32> {
+    let mut preimage = Bytes::new(env);
+    preimage.append(&Bytes::from_slice(env, &prev.to_array()));
+    preimage.append(&env.current_contract_address().to_xdr(env));
+    preimage.append(&Bytes::from_slice(env, &env.ledger().network_id().to_array()));
+    preimage.append(&Bytes::from_slice(env, &remittance_id.to_le_bytes()));
+    preimage.append(&sender.to_xdr(env));
+    preimage.append(&recipient.to_xdr(env));
+    preimage.append(&Bytes::from_slice(env, &amount.to_le_bytes()));
+    preimage.append(&Bytes::from_slice(env, &timestamp.to_le_bytes()));
+
+    env.crypto().sha256(&preimage).into()
 }
 
-pub fn set_paused(env: &Env, paused: bool) {
-    env.storage().instance().set(&DataKey::Paused, &paused);
+/// Extends the settlement hash chain with a new settlement and records the
+/// resulting link both as the new chain head and under the remittance's own
+/// `SettlementHash` entry.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `remittance_id` - Remittance ID being settled
+/// * `sender` - Sender address of the remittance
+/// * `recipient` - Recipient address of the remittance
+/// * `amount` - Settled amount
+/// * `timestamp` - Ledger timestamp at settlement time
+///
+/// # Returns
+///
+/// The new chain head, `H_n`.
+pub fn set_settlement_hash(
+    env: &Env,
+    remittance_id: u64,
+    sender: &Address,
+    recipient: &Address,
+    amount: i128,
+    timestamp: u64,
+) -> BytesN<32> {
+    let prev = get_hash_chain_head(env);
+    let next = compute_settlement_hash(env, &prev, remittance_id, sender, recipient, amount, timestamp);
+
+    let key = DataKey::SettlementHash(remittance_id);
+    env.storage().persistent().set(&key, &next);
+    extend_persistent_ttl(env, &key);
+    set_hash_chain_head(env, &next);
+
+    next
+}
+
+/// Re-derives a settlement's hash-chain link and verifies it against the
+/// stored value, detecting tampering with the settlement record or the chain.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `remittance_id` - Remittance ID to verify
+/// * `expected_prev` - The chain head expected to have preceded this settlement
+/// * `sender` - Sender address recorded for the remittance
+/// * `recipient` - Recipient address recorded for the remittance
+/// * `amount` - Settled amount recorded for the remittance
+/// * `timestamp` - Ledger timestamp recorded for the settlement
+///
+/// # Errors
+///
+/// * `ContractError::SettlementNotFound` - No hash is recorded for this remittance ID
+/// * `ContractError::HashChainMismatch` - The recomputed link does not match the stored one
+pub fn verify_settlement(
+    env: &Env,
+    remittance_id: u64,
+    expected_prev: &BytesN<32>,
+    sender: &Address,
+    recipient: &Address,
+    amount: i128,
+    timestamp: u64,
+) -> Result<(), ContractError> {
+    let stored = get_settlement_hash(env, remittance_id).ok_or(ContractError::SettlementNotFound)?;
+    let recomputed = compute_settlement_hash(
+        env,
+        expected_prev,
+        remittance_id,
+        sender,
+        recipient,
+        amount,
+        timestamp,
+    );
+
+    if recomputed != stored {
+        return Err(ContractError::HashChainMismatch);
+    }
+
+    Ok(())
+}
+
+// === Circuit Breaker ===
+// Operation-level pause flags, combined into a single bitmask so an incident
+// response can halt one category of operation without an all-or-nothing kill switch.
+
+/// Guards `confirm_payout` and other settlement/payout entry points.
+pub const PAUSE_SETTLEMENTS: u32 = 1 << 0;
+/// Guards agent registration entry points.
+pub const PAUSE_AGENT_REGISTRATION: u32 = 1 << 1;
+/// Guards fee withdrawal entry points.
+pub const PAUSE_FEE_WITHDRAWAL: u32 = 1 << 2;
+/// Guards admin-management entry points (add/remove admin, role changes).
+pub const PAUSE_ADMIN_CHANGES: u32 = 1 << 3;
+
+fn get_pause_flags(env: &Env) -> u32 {
+    env.storage().instance().get(&DataKey::Paused).unwrap_or(0)
+}
+
+fn set_pause_flags(env: &Env, flags: u32) {
+    env.storage().instance().set(&DataKey::Paused, &flags);
+}
+
+/// Pauses a single operation, leaving all others unaffected.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `flag` - One of the `PAUSE_*` operation flags (or a bitwise OR of several)
+pub fn pause_operation(env: &Env, flag: u32) {
+    let flags = get_pause_flags(env);
+    set_pause_flags(env, flags | flag);
+}
+
+/// Resumes a single operation, leaving all others unaffected.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `flag` - One of the `PAUSE_*` operation flags (or a bitwise OR of several)
+pub fn resume_operation(env: &Env, flag: u32) {
+    let flags = get_pause_flags(env);
+    set_pause_flags(env, flags & !flag);
+}
+
+/// Checks whether a given operation is currently paused.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `flag` - One of the `PAUSE_*` operation flags
+///
+/// # Returns
+///
+/// * `true` - The operation is paused
+/// * `false` - The operation is not paused
+pub fn is_operation_paused(env: &Env, flag: u32) -> bool {
+    get_pause_flags(env) & flag != 0
 }
 
 pub fn set_rate_limit_cooldown(env: &Env, cooldown_seconds: u64) {
@@ -355,9 +650,9 @@ pub fn get_rate_limit_cooldown(env: &Env) -> Result<u64, ContractError> {
 }
 
 pub fn set_last_settlement_time(env: &Env, sender: &Address, timestamp: u64) {
-    env.storage()
-        .persistent()
-        .set(&DataKey::LastSettlementTime(sender.clone()), &timestamp);
+    let key = DataKey::LastSettlementTime(sender.clone());
+    env.storage().persistent().set(&key, &timestamp);
+    extend_persistent_ttl(env, &key);
 }
 
 pub fn get_last_settlement_time(env: &Env, sender: &Address) -> Option<u64> {
@@ -386,15 +681,52 @@ pub fn check_rate_limit(env: &Env, sender: &Address) -> Result<(), ContractError
     Ok(())
 }
 
+/// Retrieves the last nonce consumed by a sender, if any.
+pub fn get_sender_nonce(env: &Env, sender: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SenderNonce(sender.clone()))
+        .unwrap_or(0)
+}
+
+/// Consumes a sender's next nonce, rejecting anything but a strict successor
+/// of the last consumed nonce. This makes each authorized settlement
+/// executable exactly once: a replayed submission carries an already-consumed
+/// nonce and is rejected.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `sender` - Sender address the nonce belongs to
+/// * `submitted_nonce` - Nonce submitted with the settlement authorization
+///
+/// # Errors
+///
+/// * `ContractError::InvalidNonce` - `submitted_nonce` is not exactly one greater than
+///   the sender's last consumed nonce
+pub fn consume_nonce(env: &Env, sender: &Address, submitted_nonce: u64) -> Result<(), ContractError> {
+    let expected = get_sender_nonce(env, sender) + 1;
+
+    if submitted_nonce != expected {
+        return Err(ContractError::InvalidNonce);
+    }
+
+    let key = DataKey::SenderNonce(sender.clone());
+    env.storage().persistent().set(&key, &submitted_nonce);
+    extend_persistent_ttl(env, &key);
+
+    Ok(())
+}
+
 pub fn set_daily_limit(env: &Env, currency: &String, country: &String, limit: i128) {
     let daily_limit = DailyLimit {
         currency: currency.clone(),
         country: country.clone(),
         limit,
     };
-    env.storage()
-        .persistent()
-        .set(&DataKey::DailyLimit(currency.clone(), country.clone()), &daily_limit);
+    let key = DataKey::DailyLimit(currency.clone(), country.clone());
+    env.storage().persistent().set(&key, &daily_limit);
+    extend_persistent_ttl(env, &key);
 }
 
 pub fn get_daily_limit(env: &Env, currency: &String, country: &String) -> Option<DailyLimit> {
@@ -411,9 +743,32 @@ pub fn get_user_transfers(env: &Env, user: &Address) -> Vec<TransferRecord> {
 }
 
 pub fn set_user_transfers(env: &Env, user: &Address, transfers: &Vec<TransferRecord>) {
-    env.storage()
-        .persistent()
-        .set(&DataKey::UserTransfers(user.clone()), transfers);
+    let key = DataKey::UserTransfers(user.clone());
+    env.storage().persistent().set(&key, transfers);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Proactively refreshes a user's transfer-history TTL without rewriting it.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `user` - User address whose transfer history should be refreshed
+///
+/// # Errors
+///
+/// * `ContractError::EntryArchived` - No transfer history is currently readable for this
+///   user (see the TTL Management notes above on why this can't be distinguished from
+///   none ever having been recorded)
+pub fn bump_user_data(env: &Env, user: &Address) -> Result<(), ContractError> {
+    let key = DataKey::UserTransfers(user.clone());
+
+    if !env.storage().persistent().has(&key) {
+        return Err(ContractError::EntryArchived);
+    }
+
+    extend_persistent_ttl(env, &key);
+    Ok(())
 }
 
 // === Admin Role Management ===
@@ -466,3 +821,170 @@ pub fn set_token_whitelisted(env: &Env, token: &Address, whitelisted: bool) {
         .persistent()
         .set(&DataKey::TokenWhitelisted(token.clone()), &whitelisted);
 }
+
+// === Migration ===
+
+/// Tracks an in-progress batched migration of remittance state to an upgraded
+/// contract. `running_hash` accumulates `sha256(running_hash || serialize(batch))`
+/// over every imported batch so it can be checked against `expected_snapshot_hash`
+/// once all batches have landed.
+#[contracttype]
+#[derive(Clone)]
+pub struct MigrationState {
+    pub active: bool,
+    pub next_batch: u32,
+    pub expected_snapshot_hash: BytesN<32>,
+    pub running_hash: BytesN<32>,
+}
+
+fn get_migration_state(env: &Env) -> Option<MigrationState> {
+    env.storage().instance().get(&DataKey::MigrationState)
+}
+
+fn set_migration_state(env: &Env, state: &MigrationState) {
+    env.storage().instance().set(&DataKey::MigrationState, state);
+}
+
+/// Starts a batched migration, recording the snapshot hash that the imported
+/// batches must fold up to before the migration can be finalized.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `snapshot_hash` - Expected hash of the full imported snapshot
+///
+/// # Errors
+///
+/// * `ContractError::MigrationInProgress` - A migration is already active
+pub fn begin_migration(env: &Env, snapshot_hash: BytesN<32>) -> Result<(), ContractError> {
+    if let Some(state) = get_migration_state(env) {
+        if state.active {
+            return Err(ContractError::MigrationInProgress);
+        }
+    }
+
+    set_migration_state(
+        env,
+        &MigrationState {
+            active: true,
+            next_batch: 0,
+            expected_snapshot_hash: snapshot_hash,
+            running_hash: BytesN::from_array(env, &[0u8; 32]),
+        },
+    );
+
+    Ok(())
+}
+
+/// Imports one batch of remittance records as part of an active migration,
+/// writing each record to persistent storage and folding the batch into the
+/// running snapshot hash. This does not mark any of the imported remittances
+/// as settled — a `Remittance` record exists independent of payout, and this
+/// batch carries no settled/unsettled flag to gate on. Use
+/// `import_settlement_hash` separately for IDs that were genuinely settled in
+/// the source contract.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `batch_no` - Sequence number of this batch; must equal the next expected batch
+/// * `records` - Remittance records contained in this batch
+///
+/// # Errors
+///
+/// * `ContractError::MigrationNotActive` - No migration is currently active
+/// * `ContractError::InvalidMigrationBatch` - `batch_no` is not the next expected batch
+pub fn import_batch(env: &Env, batch_no: u32, records: Vec<Remittance>) -> Result<(), ContractError> {
+    let mut state = get_migration_state(env).ok_or(ContractError::MigrationNotActive)?;
+
+    if !state.active {
+        return Err(ContractError::MigrationNotActive);
+    }
+
+    if batch_no != state.next_batch {
+        return Err(ContractError::InvalidMigrationBatch);
+    }
+
+    let mut preimage = Bytes::new(env);
+    preimage.append(&Bytes::from_slice(env, &state.running_hash.to_array()));
+    preimage.append(&records.clone().to_xdr(env));
+    state.running_hash = env.crypto().sha256(&preimage).into();
+
+    for record in records.iter() {
+        // A `Remittance` record exists from creation, independent of payout — do
+        // not mark it settled here. Use `import_settlement_hash` separately for
+        // the subset of IDs that were genuinely settled in the source contract.
+        set_remittance(env, record.id, &record);
+    }
+
+    state.next_batch += 1;
+    set_migration_state(env, &state);
+
+    Ok(())
+}
+
+/// Carries over a settlement hash verbatim from the source contract for a
+/// remittance ID that was genuinely settled there.
+///
+/// This stores `hash` exactly as given rather than recomputing it with
+/// `compute_settlement_hash`: that computation binds this contract's own
+/// address and the ledger network ID for domain separation (see chunk0-6),
+/// so a recomputed link here would produce different bytes than whatever the
+/// source contract actually recorded — it would start a brand-new chain, not
+/// preserve the old one. This does not advance `HashChainHead`, since the
+/// imported link was never part of this contract's own chain to begin with;
+/// only the per-remittance record is carried over, for auditing that specific
+/// settlement against the source contract's history.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+/// * `remittance_id` - Remittance ID that was settled in the source contract
+/// * `hash` - The source contract's recorded settlement hash for this ID
+///
+/// # Errors
+///
+/// * `ContractError::MigrationNotActive` - No migration is currently active
+pub fn import_settlement_hash(env: &Env, remittance_id: u64, hash: BytesN<32>) -> Result<(), ContractError> {
+    let state = get_migration_state(env).ok_or(ContractError::MigrationNotActive)?;
+
+    if !state.active {
+        return Err(ContractError::MigrationNotActive);
+    }
+
+    let key = DataKey::SettlementHash(remittance_id);
+    env.storage().persistent().set(&key, &hash);
+    extend_persistent_ttl(env, &key);
+
+    Ok(())
+}
+
+/// Finalizes an active migration, verifying that the folded hash of every
+/// imported batch matches the expected snapshot hash before clearing the
+/// active flag.
+///
+/// # Arguments
+///
+/// * `env` - The contract execution environment
+///
+/// # Errors
+///
+/// * `ContractError::MigrationNotActive` - No migration is currently active
+/// * `ContractError::InvalidMigrationHash` - The accumulated batch hash does not match
+///   `expected_snapshot_hash`
+pub fn finalize_migration(env: &Env) -> Result<(), ContractError> {
+    let mut state = get_migration_state(env).ok_or(ContractError::MigrationNotActive)?;
+
+    if !state.active {
+        return Err(ContractError::MigrationNotActive);
+    }
+
+    if state.running_hash != state.expected_snapshot_hash {
+        return Err(ContractError::InvalidMigrationHash);
+    }
+
+    state.active = false;
+    set_migration_state(env, &state);
+
+    Ok(())
+}